@@ -0,0 +1,73 @@
+//! Per-event instrumentation hooks that a component's running task wraps its inbound and
+//! outbound event streams with, feeding [`state`](super::state)'s live per-component throughput
+//! counters and event tap so
+//! `component_throughput`/`component_throughput_all`/`component_events_tapped` see real data
+//! instead of sitting permanently idle.
+//!
+//! The topology code that owns each component's task (outside this module) is expected to
+//! apply [`instrument_throughput_in`] to the stream it reads from its inputs,
+//! [`instrument_throughput_out`] to the stream it emits, and [`instrument_tap`] to whichever of
+//! those is most useful to sample.
+//!
+//! `total_errors` isn't wired by anything here: a component's processing errors surface via
+//! `crate::internal_events`, not as items on its event stream, and that plumbing lives outside
+//! this module tree. Until it's wired up, `total_errors` stays at its initial `0`.
+
+use futures::{Stream, StreamExt};
+
+use super::state;
+use super::throughput::ThroughputDelta;
+use crate::event::Event;
+
+/// Counts every event flowing into `component_name` from its upstream inputs.
+pub fn instrument_throughput_in<S>(component_name: String, events: S) -> impl Stream<Item = Event>
+where
+    S: Stream<Item = Event>,
+{
+    events.inspect(move |event| {
+        let bytes = serde_json::to_vec(event).map(|b| b.len()).unwrap_or(0) as i64;
+
+        state::record_throughput_delta(
+            &component_name,
+            ThroughputDelta {
+                events_in: 1,
+                events_out: 0,
+                bytes,
+                errors: 0,
+            },
+        );
+    })
+}
+
+/// Counts every event `component_name` emits downstream.
+pub fn instrument_throughput_out<S>(component_name: String, events: S) -> impl Stream<Item = Event>
+where
+    S: Stream<Item = Event>,
+{
+    events.inspect(move |event| {
+        let bytes = serde_json::to_vec(event).map(|b| b.len()).unwrap_or(0) as i64;
+
+        state::record_throughput_delta(
+            &component_name,
+            ThroughputDelta {
+                events_in: 0,
+                events_out: 1,
+                bytes,
+                errors: 0,
+            },
+        );
+    })
+}
+
+/// Samples every event flowing through `events`, pushing a serialized copy into
+/// `component_name`'s tap for any `component_events_tapped` subscribers.
+pub fn instrument_tap<S>(component_name: String, events: S) -> impl Stream<Item = Event>
+where
+    S: Stream<Item = Event>,
+{
+    events.inspect(move |event| {
+        if let Ok(json) = serde_json::to_value(event) {
+            state::push_tapped_event(&component_name, json);
+        }
+    })
+}
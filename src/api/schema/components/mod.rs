@@ -1,14 +1,26 @@
+pub mod instrumentation;
+pub mod redactor;
+pub mod selector;
 pub mod sink;
 pub mod source;
 pub mod state;
+pub mod throughput;
 pub mod transform;
 
 use crate::config::Config;
-use async_graphql::{Interface, Object, Subscription};
+use async_graphql::{Interface, Object, Subscription, Union};
 use lazy_static::lazy_static;
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use tokio::stream::{Stream, StreamExt};
 
+use selector::Selector;
+use throughput::ComponentThroughput;
+
+/// Default cap on how many tapped events a `component_events_tapped` subscriber is sent per
+/// second, used when the caller doesn't provide `max_events_per_sec`.
+const DEFAULT_MAX_TAPPED_EVENTS_PER_SEC: u32 = 10;
+
 #[derive(Debug, Clone, Interface)]
 #[graphql(
     field(name = "name", type = "String"),
@@ -20,29 +32,124 @@ pub enum Component {
     Sink(sink::Sink),
 }
 
+impl Component {
+    fn name(&self) -> &str {
+        match self {
+            Component::Source(s) => &s.0.name,
+            Component::Transform(t) => &t.0.name,
+            Component::Sink(s) => &s.0.name,
+        }
+    }
+
+    fn component_type(&self) -> &str {
+        match self {
+            Component::Source(s) => &s.0.component_type,
+            Component::Transform(t) => &t.0.component_type,
+            Component::Sink(s) => &s.0.component_type,
+        }
+    }
+
+    /// The component's kind — `"source"`, `"transform"`, or `"sink"` — as opposed to its
+    /// specific plugin type (e.g. `"remap"`, `"file"`, `"http"`, from [`Component::component_type`]).
+    /// This is what a selector's `component_type_glob` segment is matched against, so that
+    /// `transform:remap_*` means "transforms named like remap_*", not "components whose plugin
+    /// type is literally `transform`".
+    fn kind(&self) -> &'static str {
+        match self {
+            Component::Source(_) => "source",
+            Component::Transform(_) => "transform",
+            Component::Sink(_) => "sink",
+        }
+    }
+
+    /// Names of the components that feed this component, empty for sources.
+    fn inputs(&self) -> &[String] {
+        match self {
+            Component::Source(_) => &[],
+            Component::Transform(t) => &t.0.inputs,
+            Component::Sink(s) => &s.0.inputs,
+        }
+    }
+
+    /// Whether `self` and `other` (the same component name, from two successive configs)
+    /// differ in a way that should be reported as a reconfiguration.
+    fn reconfigured(&self, other: &Component) -> bool {
+        if self.component_type() != other.component_type() || self.inputs() != other.inputs() {
+            return true;
+        }
+
+        match (self, other) {
+            (Component::Source(a), Component::Source(b)) => a.0.output_type != b.0.output_type,
+            _ => false,
+        }
+    }
+}
+
+/// Parses the `selector` argument accepted by [`ComponentsQuery`]'s resolvers, surfacing
+/// malformed selectors as a GraphQL field error rather than panicking.
+fn parse_selectors(selectors: Option<Vec<String>>) -> async_graphql::Result<Vec<Selector>> {
+    match selectors {
+        Some(selectors) => {
+            Selector::parse_all(&selectors).map_err(async_graphql::Error::new)
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
 #[derive(Default)]
 pub struct ComponentsQuery;
 
 #[Object]
 impl ComponentsQuery {
-    /// Configured components (sources/transforms/sinks)
-    async fn components(&self) -> Vec<Component> {
-        state::filter_components(|(_name, components)| Some(components.clone()))
+    /// Configured components (sources/transforms/sinks), optionally narrowed by one or more
+    /// `component_type_glob:name_glob` selectors
+    async fn components(
+        &self,
+        selector: Option<Vec<String>>,
+    ) -> async_graphql::Result<Vec<Component>> {
+        let selectors = parse_selectors(selector)?;
+        Ok(state::filter_components(|(_, c)| {
+            selector::matches_any(&selectors, c.kind(), c.name()).then(|| c.clone())
+        }))
     }
 
-    /// Configured sources
-    async fn sources(&self) -> Vec<source::Source> {
-        state::get_sources()
+    /// Configured sources, optionally narrowed by one or more `component_type_glob:name_glob`
+    /// selectors
+    async fn sources(
+        &self,
+        selector: Option<Vec<String>>,
+    ) -> async_graphql::Result<Vec<source::Source>> {
+        let selectors = parse_selectors(selector)?;
+        Ok(state::get_sources()
+            .into_iter()
+            .filter(|s| selector::matches_any(&selectors, "source", &s.0.name))
+            .collect())
     }
 
-    /// Configured transforms
-    async fn transforms(&self) -> Vec<transform::Transform> {
-        state::get_transforms()
+    /// Configured transforms, optionally narrowed by one or more `component_type_glob:name_glob`
+    /// selectors
+    async fn transforms(
+        &self,
+        selector: Option<Vec<String>>,
+    ) -> async_graphql::Result<Vec<transform::Transform>> {
+        let selectors = parse_selectors(selector)?;
+        Ok(state::get_transforms()
+            .into_iter()
+            .filter(|t| selector::matches_any(&selectors, "transform", &t.0.name))
+            .collect())
     }
 
-    /// Configured sinks
-    async fn sinks(&self) -> Vec<sink::Sink> {
-        state::get_sinks()
+    /// Configured sinks, optionally narrowed by one or more `component_type_glob:name_glob`
+    /// selectors
+    async fn sinks(
+        &self,
+        selector: Option<Vec<String>>,
+    ) -> async_graphql::Result<Vec<sink::Sink>> {
+        let selectors = parse_selectors(selector)?;
+        Ok(state::get_sinks()
+            .into_iter()
+            .filter(|s| selector::matches_any(&selectors, "sink", &s.0.name))
+            .collect())
     }
 }
 
@@ -50,6 +157,7 @@ impl ComponentsQuery {
 enum ComponentChanged {
     Added(Component),
     Removed(Component),
+    Reconfigured { old: Component, new: Component },
 }
 
 lazy_static! {
@@ -59,6 +167,59 @@ lazy_static! {
     };
 }
 
+/// A component that was newly added to the configuration.
+#[derive(Clone, Debug)]
+pub struct ComponentAdded(Component);
+
+#[Object]
+impl ComponentAdded {
+    /// The component that was added
+    async fn component(&self) -> Component {
+        self.0.clone()
+    }
+}
+
+/// A component that was removed from the configuration.
+#[derive(Clone, Debug)]
+pub struct ComponentRemoved(Component);
+
+#[Object]
+impl ComponentRemoved {
+    /// The component that was removed
+    async fn component(&self) -> Component {
+        self.0.clone()
+    }
+}
+
+/// A component whose `component_type`, `inputs`, or other per-variant field (e.g. a source's
+/// `output_type`) changed across a config reload.
+#[derive(Clone, Debug)]
+pub struct ComponentReconfigured {
+    old: Component,
+    new: Component,
+}
+
+#[Object]
+impl ComponentReconfigured {
+    /// The component's configuration before the reload
+    async fn old(&self) -> Component {
+        self.old.clone()
+    }
+
+    /// The component's configuration after the reload
+    async fn new(&self) -> Component {
+        self.new.clone()
+    }
+}
+
+/// A single component change, as emitted by the unified `component_changed` subscription.
+#[derive(Clone, Debug, Union)]
+pub enum ComponentChangedEvent {
+    Added(ComponentAdded),
+    Removed(ComponentRemoved),
+    Reconfigured(ComponentReconfigured),
+}
+
 #[derive(Debug, Default)]
 pub struct ComponentsSubscription;
 
@@ -85,6 +246,79 @@ impl ComponentsSubscription {
                 _ => None,
             })
     }
+
+    /// Subscribes to throughput updates for a single named component
+    async fn component_throughput(
+        &self,
+        component_name: String,
+    ) -> async_graphql::Result<impl Stream<Item = ComponentThroughput>> {
+        state::watch_throughput(&component_name).ok_or_else(|| {
+            async_graphql::Error::new(format!("unknown component: {}", component_name))
+        })
+    }
+
+    /// Subscribes to throughput updates for every currently configured component
+    async fn component_throughput_all(&self) -> impl Stream<Item = ComponentThroughput> {
+        futures::stream::select_all(state::watch_all_throughput())
+    }
+
+    /// Subscribes to every component change (added, removed, or reconfigured) as a single
+    /// stream, so UIs can render a live config-reload diff
+    async fn component_changed(&self) -> impl Stream<Item = ComponentChangedEvent> {
+        COMPONENT_CHANGED
+            .subscribe()
+            .into_stream()
+            .filter_map(|c| match c {
+                Ok(ComponentChanged::Added(c)) => {
+                    Some(ComponentChangedEvent::Added(ComponentAdded(c)))
+                }
+                Ok(ComponentChanged::Removed(c)) => {
+                    Some(ComponentChangedEvent::Removed(ComponentRemoved(c)))
+                }
+                Ok(ComponentChanged::Reconfigured { old, new }) => Some(
+                    ComponentChangedEvent::Reconfigured(ComponentReconfigured { old, new }),
+                ),
+                Err(_) => None,
+            })
+    }
+
+    /// Subscribes to a rate-limited, redacted sample of the events flowing through a named
+    /// source or sink, useful for debugging a live pipeline. Each sampled event is serialized
+    /// to JSON and returned as a string after `redactions` (regex patterns) have been applied
+    /// to its string fields.
+    async fn component_events_tapped(
+        &self,
+        component_name: String,
+        redactions: Option<Vec<String>>,
+        max_events_per_sec: Option<u32>,
+    ) -> async_graphql::Result<impl Stream<Item = String>> {
+        let redactor = redactor::redactor_for(redactions.unwrap_or_default())
+            .map_err(async_graphql::Error::new)?;
+
+        let rx = state::tap(&component_name).ok_or_else(|| {
+            async_graphql::Error::new(format!("unknown component: {}", component_name))
+        })?;
+
+        let max_events_per_sec = max_events_per_sec
+            .unwrap_or(DEFAULT_MAX_TAPPED_EVENTS_PER_SEC)
+            .max(1);
+        let min_interval = Duration::from_secs(1) / max_events_per_sec;
+        let mut last_sent = Instant::now() - min_interval;
+
+        Ok(rx
+            .into_stream()
+            .filter_map(|event| event.ok())
+            .filter_map(move |mut event| {
+                let now = Instant::now();
+                if now.duration_since(last_sent) < min_interval {
+                    return None;
+                }
+                last_sent = now;
+
+                redactor.redact(&mut event);
+                Some(event.to_string())
+            }))
+    }
 }
 
 /// Update the 'global' configuration that will be consumed by component queries
@@ -151,6 +385,17 @@ pub fn update_config(config: &Config) {
             ));
         });
 
+    // Publish all components present in both the old and new config whose fields changed
+    existing_component_names
+        .intersection(&new_component_names)
+        .for_each(|name| {
+            let old = state::component_by_name(name);
+            let new = new_components.get(name).unwrap().clone();
+            if old.reconfigured(&new) {
+                let _ = COMPONENT_CHANGED.send(ComponentChanged::Reconfigured { old, new });
+            }
+        });
+
     // Override the old component state
     state::update(new_components);
 }
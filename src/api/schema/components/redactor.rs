@@ -0,0 +1,143 @@
+//! Pattern-based redaction for tapped events, mirroring how Fuchsia's archivist applies a
+//! `Redactor` to log output before exposing it.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::Value;
+
+/// Upper bound on the number of distinct redaction lists cached at once. Subscribers supply
+/// this list directly, so an unbounded cache would let client input grow memory without limit;
+/// once full, the oldest entry is evicted to make room for a new one.
+const MAX_CACHED_REDACTORS: usize = 64;
+
+const REDACTED: &str = "<REDACTED>";
+
+/// Replaces every match of a fixed set of regex patterns with [`REDACTED`] in every string
+/// field of a sampled event.
+#[derive(Debug)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    fn new(patterns: &[String]) -> Result<Self, String> {
+        let patterns = patterns
+            .iter()
+            .map(|p| {
+                Regex::new(p).map_err(|e| format!("invalid redaction pattern `{}`: {}", p, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { patterns })
+    }
+
+    /// Redacts every string field of `value`, recursing into arrays and objects.
+    pub fn redact(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => {
+                for pattern in &self.patterns {
+                    if pattern.is_match(s) {
+                        *s = pattern.replace_all(s, REDACTED).into_owned();
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|v| self.redact(v)),
+            Value::Object(map) => map.values_mut().for_each(|v| self.redact(v)),
+            _ => {}
+        }
+    }
+}
+
+/// A redactor cache bounded to [`MAX_CACHED_REDACTORS`] entries, evicted oldest-first.
+#[derive(Default)]
+struct RedactorCache {
+    entries: HashMap<Vec<String>, Arc<Redactor>>,
+    insertion_order: VecDeque<Vec<String>>,
+}
+
+impl RedactorCache {
+    fn get(&self, patterns: &[String]) -> Option<Arc<Redactor>> {
+        self.entries.get(patterns).cloned()
+    }
+
+    fn insert(&mut self, patterns: Vec<String>, redactor: Arc<Redactor>) {
+        // Two concurrent misses for the same list can both try to insert it; queueing it twice
+        // would let a later eviction remove nothing and the cache grow past its bound.
+        if self.entries.contains_key(&patterns) {
+            return;
+        }
+        if self.entries.len() >= MAX_CACHED_REDACTORS {
+            if let Some(oldest) = self.insertion_order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.insertion_order.push_back(patterns.clone());
+        self.entries.insert(patterns, redactor);
+    }
+}
+
+lazy_static! {
+    // Keyed by the exact redaction list a subscriber passed in, so that repeated subscriptions
+    // with the same patterns don't recompile the same regexes.
+    static ref REDACTOR_CACHE: RwLock<RedactorCache> = RwLock::new(RedactorCache::default());
+}
+
+/// Returns a cached [`Redactor`] for `patterns`, compiling (and caching) it on first use.
+pub fn redactor_for(patterns: Vec<String>) -> Result<Arc<Redactor>, String> {
+    if let Some(redactor) = REDACTOR_CACHE.read().unwrap().get(&patterns) {
+        return Ok(redactor);
+    }
+
+    let redactor = Arc::new(Redactor::new(&patterns)?);
+    REDACTOR_CACHE
+        .write()
+        .unwrap()
+        .insert(patterns, Arc::clone(&redactor));
+    Ok(redactor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redacts_matching_string_fields_recursively() {
+        let redactor = Redactor::new(&["secret-\\d+".to_string()]).unwrap();
+        let mut value = json!({
+            "message": "token secret-123 seen",
+            "nested": ["fine", "secret-456"],
+        });
+
+        redactor.redact(&mut value);
+
+        assert_eq!(value["message"], "token <REDACTED> seen");
+        assert_eq!(value["nested"][0], "fine");
+        assert_eq!(value["nested"][1], "<REDACTED>");
+    }
+
+    #[test]
+    fn invalid_pattern_is_rejected() {
+        assert!(Redactor::new(&["(".to_string()]).is_err());
+    }
+
+    #[test]
+    fn cache_evicts_oldest_entry_once_full() {
+        let mut cache = RedactorCache::default();
+        for i in 0..MAX_CACHED_REDACTORS {
+            let patterns = vec![format!("pattern-{}", i)];
+            cache.insert(patterns.clone(), Arc::new(Redactor::new(&patterns).unwrap()));
+        }
+        assert!(cache.get(&["pattern-0".to_string()]).is_some());
+
+        let overflow = vec!["pattern-overflow".to_string()];
+        cache.insert(overflow.clone(), Arc::new(Redactor::new(&overflow).unwrap()));
+
+        assert!(cache.get(&["pattern-0".to_string()]).is_none());
+        assert!(cache.get(&overflow).is_some());
+        assert_eq!(cache.entries.len(), MAX_CACHED_REDACTORS);
+    }
+}
@@ -0,0 +1,101 @@
+//! Parsing and matching for component selectors, modeled on Fuchsia's diagnostics selector
+//! syntax: a string of the form `component_type_glob:name_glob`, e.g. `transform:remap_*` or
+//! `sink:**`.
+
+/// A single parsed component selector.
+#[derive(Debug, Clone)]
+pub struct Selector {
+    component_type_glob: String,
+    name_glob: String,
+}
+
+impl Selector {
+    /// Parses a selector string of the form `component_type_glob:name_glob`.
+    pub fn parse(selector: &str) -> Result<Self, String> {
+        let (component_type_glob, name_glob) = selector.split_once(':').ok_or_else(|| {
+            format!(
+                "invalid selector `{}`: expected `component_type_glob:name_glob`",
+                selector
+            )
+        })?;
+
+        if component_type_glob.is_empty() || name_glob.is_empty() {
+            return Err(format!(
+                "invalid selector `{}`: component type and name glob must not be empty",
+                selector
+            ));
+        }
+
+        Ok(Self {
+            component_type_glob: component_type_glob.to_string(),
+            name_glob: name_glob.to_string(),
+        })
+    }
+
+    /// Parses a list of selector strings, failing on the first malformed entry.
+    pub fn parse_all(selectors: &[String]) -> Result<Vec<Self>, String> {
+        selectors.iter().map(|s| Self::parse(s)).collect()
+    }
+
+    /// Returns whether this selector matches the given component type and name.
+    pub fn matches(&self, component_type: &str, name: &str) -> bool {
+        glob_match(&self.component_type_glob, component_type) && glob_match(&self.name_glob, name)
+    }
+}
+
+/// Returns whether `component_type`/`name` match at least one of `selectors`. An empty selector
+/// list matches everything, so that omitting the argument keeps the previous "return all"
+/// behavior.
+pub fn matches_any(selectors: &[Selector], component_type: &str, name: &str) -> bool {
+    selectors.is_empty() || selectors.iter().any(|s| s.matches(component_type, name))
+}
+
+/// Matches `value` against a glob `pattern` where `*`/`**` match any (possibly empty) run of
+/// characters. Only a single wildcard is supported per glob, which is sufficient for matching
+/// component names and types.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    if pattern == "*" || pattern == "**" {
+        return true;
+    }
+
+    match pattern.split_once('*') {
+        None => pattern == value,
+        Some((prefix, suffix)) => {
+            let suffix = suffix.trim_start_matches('*');
+            value.len() >= prefix.len() + suffix.len()
+                && value.starts_with(prefix)
+                && value.ends_with(suffix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_kind_and_name_glob() {
+        let selector = Selector::parse("transform:remap_*").unwrap();
+        assert!(selector.matches("transform", "remap_foo"));
+        assert!(!selector.matches("transform", "other"));
+        assert!(!selector.matches("sink", "remap_foo"));
+    }
+
+    #[test]
+    fn double_star_matches_everything() {
+        let selector = Selector::parse("sink:**").unwrap();
+        assert!(selector.matches("sink", "anything"));
+    }
+
+    #[test]
+    fn empty_selector_list_matches_everything() {
+        assert!(matches_any(&[], "source", "anything"));
+    }
+
+    #[test]
+    fn rejects_malformed_selectors() {
+        assert!(Selector::parse("missing-colon").is_err());
+        assert!(Selector::parse(":name").is_err());
+        assert!(Selector::parse("type:").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use async_graphql::Object;
+
+use super::{state, Component};
+
+#[derive(Debug, Clone)]
+pub struct Data {
+    pub name: String,
+    pub component_type: String,
+    pub inputs: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Sink(pub Data);
+
+#[Object]
+impl Sink {
+    /// Sink name
+    pub async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// Sink type
+    pub async fn component_type(&self) -> &str {
+        &self.0.component_type
+    }
+
+    /// Names of the components whose output feeds this sink
+    pub async fn inputs(&self) -> Vec<String> {
+        self.0.inputs.clone()
+    }
+
+    /// Total events processed by this sink since it was registered
+    pub async fn total_events_processed(&self) -> i64 {
+        state::get_throughput(&self.0.name)
+            .map(|t| t.total_events_out)
+            .unwrap_or_default()
+    }
+
+    /// Current events/sec being emitted by this sink
+    pub async fn events_per_sec(&self) -> f64 {
+        state::get_throughput(&self.0.name)
+            .map(|t| t.events_per_sec)
+            .unwrap_or_default()
+    }
+
+    /// Upstream components feeding this sink
+    pub async fn sources(&self) -> Vec<Component> {
+        state::get_sources_of(&self.0.inputs)
+    }
+
+    /// Downstream components whose inputs reference this sink. Typically empty, since sinks
+    /// are terminal.
+    pub async fn consumers(&self) -> Vec<Component> {
+        state::get_consumers(&self.0.name)
+    }
+}
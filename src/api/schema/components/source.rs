@@ -0,0 +1,56 @@
+use async_graphql::Object;
+
+use super::{state, Component};
+use crate::config::DataType;
+
+#[derive(Debug, Clone)]
+pub struct Data {
+    pub name: String,
+    pub component_type: String,
+    pub output_type: DataType,
+}
+
+#[derive(Debug, Clone)]
+pub struct Source(pub Data);
+
+#[Object]
+impl Source {
+    /// Source name
+    pub async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// Source type
+    pub async fn component_type(&self) -> &str {
+        &self.0.component_type
+    }
+
+    /// Source output type
+    pub async fn output_type(&self) -> DataType {
+        self.0.output_type
+    }
+
+    /// Total events processed by this source since it was registered
+    pub async fn total_events_processed(&self) -> i64 {
+        state::get_throughput(&self.0.name)
+            .map(|t| t.total_events_out)
+            .unwrap_or_default()
+    }
+
+    /// Current events/sec being emitted by this source
+    pub async fn events_per_sec(&self) -> f64 {
+        state::get_throughput(&self.0.name)
+            .map(|t| t.events_per_sec)
+            .unwrap_or_default()
+    }
+
+    /// Upstream components feeding this source. Always empty, since sources have no inputs.
+    pub async fn sources(&self) -> Vec<Component> {
+        Vec::new()
+    }
+
+    /// Downstream components whose inputs reference this source
+    pub async fn consumers(&self) -> Vec<Component> {
+        state::get_consumers(&self.0.name)
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use lazy_static::lazy_static;
+use serde_json::Value;
+use tokio::sync::{broadcast, watch};
+
+use super::throughput::{ComponentThroughput, Counters, SharedCounters, ThroughputDelta};
+use super::{sink, source, transform, Component};
+
+/// Small buffer so that a slow `component_events_tapped` subscriber drops samples rather than
+/// applying backpressure to the hot path pushing events into the tap.
+const TAP_BUFFER_SIZE: usize = 16;
+
+lazy_static! {
+    static ref COMPONENTS: RwLock<HashMap<String, Component>> = RwLock::new(HashMap::new());
+    static ref THROUGHPUT: RwLock<HashMap<String, (SharedCounters, watch::Sender<ComponentThroughput>)>> =
+        RwLock::new(HashMap::new());
+    // Maps a component name to the names of the components whose `inputs` reference it, so
+    // that resolving `consumers` is O(1) instead of a full scan per query.
+    static ref REVERSE_EDGES: RwLock<HashMap<String, HashSet<String>>> = RwLock::new(HashMap::new());
+    static ref TAPS: RwLock<HashMap<String, broadcast::Sender<Value>>> = RwLock::new(HashMap::new());
+}
+
+/// Update the 'global' component state, used by [`super::ComponentsQuery`] and
+/// [`super::ComponentsSubscription`].
+pub fn update(new_components: HashMap<String, Component>) {
+    // Drop counters for components that no longer exist, and seed counters for newly added
+    // ones so that throughput queries/subscriptions have something to read immediately.
+    let mut throughput = THROUGHPUT.write().unwrap();
+    throughput.retain(|name, _| new_components.contains_key(name));
+    for name in new_components.keys() {
+        throughput.entry(name.clone()).or_insert_with(|| {
+            let counters = Arc::new(Counters::new(name.clone()));
+            let (tx, _) = watch::channel(counters.snapshot());
+            (counters, tx)
+        });
+    }
+    drop(throughput);
+
+    let mut reverse_edges: HashMap<String, HashSet<String>> = HashMap::new();
+    for (name, component) in new_components.iter() {
+        for input in component.inputs() {
+            reverse_edges
+                .entry(input.clone())
+                .or_default()
+                .insert(name.clone());
+        }
+    }
+    *REVERSE_EDGES.write().unwrap() = reverse_edges;
+
+    // Drop taps for components that no longer exist, and seed a tap for newly added ones so
+    // the running topology always has somewhere to push sampled events.
+    let mut taps = TAPS.write().unwrap();
+    taps.retain(|name, _| new_components.contains_key(name));
+    for name in new_components.keys() {
+        taps.entry(name.clone())
+            .or_insert_with(|| broadcast::channel(TAP_BUFFER_SIZE).0);
+    }
+    drop(taps);
+
+    *COMPONENTS.write().unwrap() = new_components;
+}
+
+/// Returns the current set of configured component names.
+pub fn get_component_names() -> HashSet<String> {
+    COMPONENTS.read().unwrap().keys().cloned().collect()
+}
+
+/// Returns the component registered under `name`.
+///
+/// # Panics
+///
+/// Panics if `name` doesn't match an existing component. Callers are expected to only pass
+/// names obtained from the component state itself.
+pub fn component_by_name(name: &str) -> Component {
+    COMPONENTS
+        .read()
+        .unwrap()
+        .get(name)
+        .cloned()
+        .expect("component didn't exist in COMPONENTS. Please report this.")
+}
+
+/// Filter the current components, returning each entry that `filter` maps to `Some`.
+pub fn filter_components<T>(filter: impl Fn((&String, &Component)) -> Option<T>) -> Vec<T> {
+    COMPONENTS.read().unwrap().iter().filter_map(filter).collect()
+}
+
+pub fn get_sources() -> Vec<source::Source> {
+    filter_components(|(_, c)| match c {
+        Component::Source(s) => Some(s.clone()),
+        _ => None,
+    })
+}
+
+pub fn get_transforms() -> Vec<transform::Transform> {
+    filter_components(|(_, c)| match c {
+        Component::Transform(t) => Some(t.clone()),
+        _ => None,
+    })
+}
+
+pub fn get_sinks() -> Vec<sink::Sink> {
+    filter_components(|(_, c)| match c {
+        Component::Sink(s) => Some(s.clone()),
+        _ => None,
+    })
+}
+
+/// Applies a throughput delta to `name`'s running counters, notifying subscribers only if the
+/// resulting snapshot actually changed.
+pub fn record_throughput_delta(name: &str, delta: ThroughputDelta) {
+    if let Some((counters, tx)) = THROUGHPUT.read().unwrap().get(name) {
+        let snapshot = counters.apply(delta);
+        tx.send_if_modified(|current| {
+            if *current == snapshot {
+                false
+            } else {
+                *current = snapshot.clone();
+                true
+            }
+        });
+    }
+}
+
+/// Returns the latest throughput snapshot for `name`, if it's a known component. A read-only
+/// query; it doesn't perturb the windowed rate that `component_throughput` subscribers see.
+pub fn get_throughput(name: &str) -> Option<ComponentThroughput> {
+    THROUGHPUT
+        .read()
+        .unwrap()
+        .get(name)
+        .map(|(counters, _)| counters.snapshot())
+}
+
+/// Subscribes to throughput updates for a single component.
+pub fn watch_throughput(name: &str) -> Option<watch::Receiver<ComponentThroughput>> {
+    THROUGHPUT
+        .read()
+        .unwrap()
+        .get(name)
+        .map(|(_, tx)| tx.subscribe())
+}
+
+/// Subscribes to throughput updates for every currently configured component.
+pub fn watch_all_throughput() -> Vec<watch::Receiver<ComponentThroughput>> {
+    THROUGHPUT
+        .read()
+        .unwrap()
+        .values()
+        .map(|(_, tx)| tx.subscribe())
+        .collect()
+}
+
+/// Resolves the components named by `inputs`, i.e. the upstream components feeding a component
+/// with those inputs. Names with no current component are skipped.
+pub fn get_sources_of(inputs: &[String]) -> Vec<Component> {
+    let components = COMPONENTS.read().unwrap();
+    inputs
+        .iter()
+        .filter_map(|name| components.get(name).cloned())
+        .collect()
+}
+
+/// Resolves the downstream components whose `inputs` reference `name`.
+pub fn get_consumers(name: &str) -> Vec<Component> {
+    let reverse_edges = REVERSE_EDGES.read().unwrap();
+    let components = COMPONENTS.read().unwrap();
+
+    reverse_edges
+        .get(name)
+        .into_iter()
+        .flatten()
+        .filter_map(|name| components.get(name).cloned())
+        .collect()
+}
+
+/// Subscribes to the tap for `name`, receiving a sample of the events flowing through it.
+pub fn tap(name: &str) -> Option<broadcast::Receiver<Value>> {
+    TAPS.read().unwrap().get(name).map(|tx| tx.subscribe())
+}
+
+/// Pushes a sampled, already-serialized event into `name`'s tap, to be picked up by any
+/// `component_events_tapped` subscribers. Called by the running topology; a no-op if nobody is
+/// subscribed, since `send` only fails when there are no receivers.
+pub fn push_tapped_event(name: &str, event: Value) {
+    if let Some(tx) = TAPS.read().unwrap().get(name) {
+        let _ = tx.send(event);
+    }
+}